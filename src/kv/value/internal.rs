@@ -1,3 +1,4 @@
+use std::any::Any;
 use std::fmt;
 
 use super::{Error, Fill, Slot};
@@ -14,13 +15,21 @@ pub(super) enum Inner<'v> {
     /// A value that can be filled.
     Fill(&'v dyn Fill),
     /// A debuggable value.
-    Debug(&'v dyn fmt::Debug),
+    Debug(&'v dyn fmt::Debug, Option<&'v dyn Any>),
     /// A displayable value.
-    Display(&'v dyn fmt::Display),
+    Display(&'v dyn fmt::Display, Option<&'v dyn Any>),
+    /// A set of key-value pairs.
+    Map(&'v dyn map_support::VisitMap<'v>),
+    /// A sequence of elements.
+    Seq(&'v dyn map_support::VisitSeq<'v>),
 
     #[cfg(feature = "kv_unstable_sval")]
     /// A structured value from `sval`.
     Sval(&'v dyn sval_support::Value),
+
+    #[cfg(feature = "kv_unstable_serde")]
+    /// A structured value from `serde`.
+    Serde(&'v dyn erased_serde::Serialize),
 }
 
 impl<'v> Inner<'v> {
@@ -33,14 +42,33 @@ impl<'v> Inner<'v> {
                 Primitive::Bool(value) => visitor.bool(value),
                 Primitive::Char(value) => visitor.char(value),
                 Primitive::Str(value) => visitor.str(value),
+                Primitive::Bytes(value) => visitor.bytes(value),
+                Primitive::Unsigned128(value) => visitor.u128(value),
+                Primitive::Signed128(value) => visitor.i128(value),
                 Primitive::None => visitor.none(),
             },
             Inner::Fill(value) => value.fill(&mut Slot::new(visitor)),
-            Inner::Debug(value) => visitor.debug(value),
-            Inner::Display(value) => visitor.display(value),
+            Inner::Debug(value, _) => visitor.debug(value),
+            Inner::Display(value, _) => visitor.display(value),
+            Inner::Map(value) => {
+                visitor.map_begin(value.len())?;
+                value.visit(&mut |k, v| {
+                    visitor.map_key(k)?;
+                    visitor.map_value(v)
+                })?;
+                visitor.map_end()
+            }
+            Inner::Seq(value) => {
+                visitor.seq_begin(value.len())?;
+                value.visit(&mut |v| visitor.seq_elem(v))?;
+                visitor.seq_end()
+            }
 
             #[cfg(feature = "kv_unstable_sval")]
             Inner::Sval(value) => visitor.sval(value),
+
+            #[cfg(feature = "kv_unstable_serde")]
+            Inner::Serde(value) => visitor.serde(value),
         }
     }
 }
@@ -58,10 +86,45 @@ pub(super) trait Visitor {
     fn bool(&mut self, v: bool) -> Result<(), Error>;
     fn char(&mut self, v: char) -> Result<(), Error>;
     fn str(&mut self, v: &str) -> Result<(), Error>;
+    fn bytes(&mut self, v: &[u8]) -> Result<(), Error> {
+        self.debug(&format_args!("{:?}", v))
+    }
+    fn u128(&mut self, v: u128) -> Result<(), Error> {
+        self.debug(&format_args!("{:?}", v))
+    }
+    fn i128(&mut self, v: i128) -> Result<(), Error> {
+        self.debug(&format_args!("{:?}", v))
+    }
     fn none(&mut self) -> Result<(), Error>;
 
+    fn map_begin(&mut self, _len: Option<usize>) -> Result<(), Error> {
+        Ok(())
+    }
+    fn map_key(&mut self, k: kv::Value) -> Result<(), Error> {
+        self.debug(&k)
+    }
+    fn map_value(&mut self, v: kv::Value) -> Result<(), Error> {
+        self.debug(&v)
+    }
+    fn map_end(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn seq_begin(&mut self, _len: Option<usize>) -> Result<(), Error> {
+        Ok(())
+    }
+    fn seq_elem(&mut self, v: kv::Value) -> Result<(), Error> {
+        self.debug(&v)
+    }
+    fn seq_end(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
     #[cfg(feature = "kv_unstable_sval")]
     fn sval(&mut self, v: &dyn sval_support::Value) -> Result<(), Error>;
+
+    #[cfg(feature = "kv_unstable_serde")]
+    fn serde(&mut self, v: &dyn erased_serde::Serialize) -> Result<(), Error>;
 }
 
 #[derive(Clone, Copy)]
@@ -72,9 +135,36 @@ pub(super) enum Primitive<'v> {
     Bool(bool),
     Char(char),
     Str(&'v str),
+    Bytes(&'v [u8]),
+    // Named to match the `Unsigned`/`Signed` rungs above rather than `U128`/`I128`.
+    Unsigned128(u128),
+    Signed128(i128),
     None,
 }
 
+impl<'v> kv::Value<'v> {
+    /// Get a value from a byte slice.
+    pub fn from_bytes(value: &'v [u8]) -> Self {
+        kv::Value {
+            inner: Inner::Primitive(Primitive::Bytes(value)),
+        }
+    }
+
+    /// Get a value from a `u128`.
+    pub fn from_u128(value: u128) -> Self {
+        kv::Value {
+            inner: Inner::Primitive(Primitive::Unsigned128(value)),
+        }
+    }
+
+    /// Get a value from an `i128`.
+    pub fn from_i128(value: i128) -> Self {
+        kv::Value {
+            inner: Inner::Primitive(Primitive::Signed128(value)),
+        }
+    }
+}
+
 mod coerce {
     use super::*;
 
@@ -88,23 +178,67 @@ mod coerce {
         }
 
         pub(in crate::kv::value) fn as_u64(&self) -> Option<u64> {
-            self.coerce().into_primitive().into_u64()
+            if let Inner::Primitive(Primitive::Unsigned(value)) = self {
+                Some(*value)
+            } else {
+                self.coerce().into_primitive().into_u64()
+            }
         }
 
         pub(in crate::kv::value) fn as_i64(&self) -> Option<i64> {
-            self.coerce().into_primitive().into_i64()
+            if let Inner::Primitive(Primitive::Signed(value)) = self {
+                Some(*value)
+            } else {
+                self.coerce().into_primitive().into_i64()
+            }
         }
 
         pub(in crate::kv::value) fn as_f64(&self) -> Option<f64> {
-            self.coerce().into_primitive().into_f64()
+            if let Inner::Primitive(Primitive::Float(value)) = self {
+                Some(*value)
+            } else {
+                self.coerce().into_primitive().into_f64()
+            }
         }
 
         pub(in crate::kv::value) fn as_char(&self) -> Option<char> {
-            self.coerce().into_primitive().into_char()
+            if let Inner::Primitive(Primitive::Char(value)) = self {
+                Some(*value)
+            } else {
+                self.coerce().into_primitive().into_char()
+            }
         }
 
         pub(in crate::kv::value) fn as_bool(&self) -> Option<bool> {
-            self.coerce().into_primitive().into_bool()
+            if let Inner::Primitive(Primitive::Bool(value)) = self {
+                Some(*value)
+            } else {
+                self.coerce().into_primitive().into_bool()
+            }
+        }
+
+        pub(in crate::kv::value) fn as_bytes(&self) -> Option<&[u8]> {
+            if let Inner::Primitive(Primitive::Bytes(value)) = self {
+                Some(value)
+            } else {
+                None
+            }
+        }
+
+        pub(in crate::kv::value) fn as_u128(&self) -> Option<u128> {
+            if let Inner::Primitive(Primitive::Unsigned128(value)) = self {
+                Some(*value)
+            } else {
+                self.coerce().into_primitive().into_u128()
+            }
+        }
+
+        pub(in crate::kv::value) fn as_i128(&self) -> Option<i128> {
+            if let Inner::Primitive(Primitive::Signed128(value)) = self {
+                Some(*value)
+            } else {
+                self.coerce().into_primitive().into_i128()
+            }
         }
 
         fn coerce(&self) -> Coerced {
@@ -146,6 +280,26 @@ mod coerce {
                     Ok(())
                 }
 
+                fn u128(&mut self, v: u128) -> Result<(), Error> {
+                    self.0 = Coerced::Primitive(if v <= u64::max_value() as u128 {
+                        Primitive::Unsigned(v as u64)
+                    } else {
+                        Primitive::Unsigned128(v)
+                    });
+                    Ok(())
+                }
+
+                fn i128(&mut self, v: i128) -> Result<(), Error> {
+                    self.0 = Coerced::Primitive(
+                        if v >= i64::min_value() as i128 && v <= i64::max_value() as i128 {
+                            Primitive::Signed(v as i64)
+                        } else {
+                            Primitive::Signed128(v)
+                        },
+                    );
+                    Ok(())
+                }
+
                 #[cfg(not(feature = "std"))]
                 fn str(&mut self, v: &str) -> Result<(), Error> {
                     Ok(())
@@ -157,6 +311,17 @@ mod coerce {
                     Ok(())
                 }
 
+                #[cfg(not(feature = "std"))]
+                fn bytes(&mut self, v: &[u8]) -> Result<(), Error> {
+                    Ok(())
+                }
+
+                #[cfg(feature = "std")]
+                fn bytes(&mut self, v: &[u8]) -> Result<(), Error> {
+                    self.0 = Coerced::Bytes(v.to_vec());
+                    Ok(())
+                }
+
                 fn none(&mut self) -> Result<(), Error> {
                     self.0 = Coerced::Primitive(Primitive::None);
                     Ok(())
@@ -167,6 +332,12 @@ mod coerce {
                     self.0 = sval_support::coerce(v);
                     Ok(())
                 }
+
+                #[cfg(feature = "kv_unstable_serde")]
+                fn serde(&mut self, v: &dyn erased_serde::Serialize) -> Result<(), Error> {
+                    self.0 = serde_support::coerce(v);
+                    Ok(())
+                }
             }
 
             let mut coerce = Coerce::new();
@@ -179,6 +350,8 @@ mod coerce {
         Primitive(Primitive<'v>),
         #[cfg(feature = "std")]
         String(String),
+        #[cfg(feature = "std")]
+        Bytes(Vec<u8>),
     }
 
     impl<'v> Coerced<'v> {
@@ -238,6 +411,22 @@ mod coerce {
                 None
             }
         }
+
+        fn into_u128(self) -> Option<u128> {
+            match self {
+                Primitive::Unsigned(value) => Some(value as u128),
+                Primitive::Unsigned128(value) => Some(value),
+                _ => None,
+            }
+        }
+
+        fn into_i128(self) -> Option<i128> {
+            match self {
+                Primitive::Signed(value) => Some(value as i128),
+                Primitive::Signed128(value) => Some(value),
+                _ => None,
+            }
+        }
     }
 
     #[cfg(feature = "std")]
@@ -250,6 +439,10 @@ mod coerce {
             pub(in crate::kv::value) fn to_str(&self) -> Option<Cow<str>> {
                 self.coerce().into_string()
             }
+
+            pub(in crate::kv::value) fn to_bytes(&self) -> Option<Cow<[u8]>> {
+                self.coerce().into_bytes()
+            }
         }
 
         impl<'v> Coerced<'v> {
@@ -260,222 +453,1540 @@ mod coerce {
                     _ => None,
                 }
             }
+
+            pub(super) fn into_bytes(self) -> Option<Cow<'v, [u8]>> {
+                match self {
+                    Coerced::Primitive(Primitive::Bytes(value)) => Some(value.into()),
+                    Coerced::Bytes(value) => Some(value.into()),
+                    _ => None,
+                }
+            }
         }
     }
 }
 
-mod fmt_support {
+mod cast {
     use super::*;
 
     impl<'v> kv::Value<'v> {
-        /// Get a value from a debuggable type.
-        pub fn from_debug<T>(value: &'v T) -> Self
-        where
-            T: fmt::Debug,
-        {
-            kv::Value {
-                inner: Inner::Debug(value),
-            }
-        }
-
-        /// Get a value from a displayable type.
-        pub fn from_display<T>(value: &'v T) -> Self
-        where
-            T: fmt::Display,
-        {
-            kv::Value {
-                inner: Inner::Display(value),
-            }
+        /// Try get a value of a specific type back from this value.
+        ///
+        /// If the value was captured from a concrete `'static` type this
+        /// recovers it directly, without running it through a `Visitor`.
+        pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+            self.inner.downcast_ref()
         }
     }
 
-    impl<'v> fmt::Debug for kv::Value<'v> {
-        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            self.visit(&mut FmtVisitor(f))?;
-
-            Ok(())
+    impl<'v> Inner<'v> {
+        pub(in crate::kv::value) fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+            match *self {
+                Inner::Primitive(ref value) => value.downcast_ref(),
+                Inner::Debug(_, any) => any.and_then(|any| any.downcast_ref()),
+                Inner::Display(_, any) => any.and_then(|any| any.downcast_ref()),
+                _ => None,
+            }
         }
     }
 
-    impl<'v> fmt::Display for kv::Value<'v> {
-        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            self.visit(&mut FmtVisitor(f))?;
-
-            Ok(())
+    impl<'v> Primitive<'v> {
+        fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+            match self {
+                Primitive::Signed(value) => (value as &dyn Any).downcast_ref(),
+                Primitive::Unsigned(value) => (value as &dyn Any).downcast_ref(),
+                Primitive::Float(value) => (value as &dyn Any).downcast_ref(),
+                Primitive::Bool(value) => (value as &dyn Any).downcast_ref(),
+                Primitive::Char(value) => (value as &dyn Any).downcast_ref(),
+                // `&'v str` isn't `'static`, so it can't be recovered through `Any`.
+                Primitive::Str(_) => None,
+                // `&'v [u8]` isn't `'static`, so it can't be recovered through `Any`.
+                Primitive::Bytes(_) => None,
+                Primitive::Unsigned128(value) => (value as &dyn Any).downcast_ref(),
+                Primitive::Signed128(value) => (value as &dyn Any).downcast_ref(),
+                Primitive::None => None,
+            }
         }
     }
 
-    struct FmtVisitor<'a, 'b: 'a>(&'a mut fmt::Formatter<'b>);
+    #[cfg(test)]
+    mod tests {
+        use super::*;
 
-    impl<'a, 'b: 'a> Visitor for FmtVisitor<'a, 'b> {
-        fn debug(&mut self, v: &dyn fmt::Debug) -> Result<(), Error> {
-            v.fmt(self.0)?;
+        #[derive(Debug, PartialEq, Eq)]
+        struct MyId(u64);
 
-            Ok(())
+        impl fmt::Display for MyId {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
         }
 
-        fn u64(&mut self, v: u64) -> Result<(), Error> {
-            self.debug(&format_args!("{:?}", v))
-        }
+        #[test]
+        fn capture_debug_roundtrips_through_downcast_ref() {
+            let id = MyId(42);
+            let value = kv::Value::capture_debug(&id);
 
-        fn i64(&mut self, v: i64) -> Result<(), Error> {
-            self.debug(&format_args!("{:?}", v))
+            assert_eq!(Some(&id), value.downcast_ref::<MyId>());
         }
 
-        fn f64(&mut self, v: f64) -> Result<(), Error> {
-            self.debug(&format_args!("{:?}", v))
-        }
+        #[test]
+        fn capture_display_roundtrips_through_downcast_ref() {
+            let id = MyId(42);
+            let value = kv::Value::capture_display(&id);
 
-        fn bool(&mut self, v: bool) -> Result<(), Error> {
-            self.debug(&format_args!("{:?}", v))
+            assert_eq!(Some(&id), value.downcast_ref::<MyId>());
         }
 
-        fn char(&mut self, v: char) -> Result<(), Error> {
-            self.debug(&format_args!("{:?}", v))
-        }
+        #[test]
+        fn from_debug_does_not_roundtrip_through_downcast_ref() {
+            let id = MyId(42);
+            let value = kv::Value::from_debug(&id);
 
-        fn str(&mut self, v: &str) -> Result<(), Error> {
-            self.debug(&format_args!("{:?}", v))
+            assert_eq!(None, value.downcast_ref::<MyId>());
         }
 
-        fn none(&mut self) -> Result<(), Error> {
-            self.debug(&format_args!("None"))
-        }
+        #[test]
+        fn from_display_does_not_roundtrip_through_downcast_ref() {
+            let id = MyId(42);
+            let value = kv::Value::from_display(&id);
 
-        #[cfg(feature = "kv_unstable_sval")]
-        fn sval(&mut self, v: &dyn sval_support::Value) -> Result<(), Error> {
-            sval_support::fmt(self.0, v)
+            assert_eq!(None, value.downcast_ref::<MyId>());
         }
     }
 }
 
-#[cfg(feature = "kv_unstable_sval")]
-pub(super) mod sval_support {
-    use super::coerce::Coerced;
+mod map_support {
     use super::*;
 
-    extern crate sval;
-
     impl<'v> kv::Value<'v> {
-        /// Get a value from a structured type.
-        pub fn from_sval<T>(value: &'v T) -> Self
+        /// Get a value from an ordered set of key-value pairs.
+        pub fn from_map<M>(value: &'v M) -> Self
         where
-            T: sval::Value,
+            M: VisitMap<'v>,
         {
             kv::Value {
-                inner: Inner::Sval(value),
+                inner: Inner::Map(value),
             }
         }
-    }
-
-    impl<'v> sval::Value for kv::Value<'v> {
-        fn stream(&self, s: &mut sval::value::Stream) -> sval::value::Result {
-            self.visit(&mut SvalVisitor(s)).map_err(Error::into_sval)?;
 
-            Ok(())
+        /// Get a value from a sequence of elements.
+        pub fn from_seq<S>(value: &'v S) -> Self
+        where
+            S: VisitSeq<'v>,
+        {
+            kv::Value {
+                inner: Inner::Seq(value),
+            }
         }
     }
 
-    pub(in kv::value) use self::sval::Value;
+    /// A set of key-value pairs that can stream themselves into a `Visitor`.
+    pub(super) trait VisitMap<'v> {
+        fn len(&self) -> Option<usize>;
 
-    pub(super) fn fmt(f: &mut fmt::Formatter, v: &dyn sval::Value) -> Result<(), Error> {
-        sval::fmt::debug(f, v)?;
-        Ok(())
+        fn visit(
+            &self,
+            visit: &mut dyn FnMut(kv::Value<'v>, kv::Value<'v>) -> Result<(), Error>,
+        ) -> Result<(), Error>;
     }
 
-    impl Error {
-        fn from_sval(_: sval::value::Error) -> Self {
-            Error::msg("`sval` serialization failed")
-        }
+    /// A sequence of elements that can stream themselves into a `Visitor`.
+    pub(super) trait VisitSeq<'v> {
+        fn len(&self) -> Option<usize>;
 
-        fn into_sval(self) -> sval::value::Error {
-            sval::value::Error::msg("`sval` serialization failed")
-        }
+        fn visit(
+            &self,
+            visit: &mut dyn FnMut(kv::Value<'v>) -> Result<(), Error>,
+        ) -> Result<(), Error>;
     }
 
-    struct SvalVisitor<'a, 'b: 'a>(&'a mut sval::value::Stream<'b>);
-
-    impl<'a, 'b: 'a> Visitor for SvalVisitor<'a, 'b> {
-        fn debug(&mut self, v: &dyn fmt::Debug) -> Result<(), Error> {
-            self.0
-                .fmt(format_args!("{:?}", v))
-                .map_err(Error::from_sval)
+    impl<'v, K, V> VisitMap<'v> for [(K, V)]
+    where
+        K: Copy + Into<kv::Value<'v>>,
+        V: Copy + Into<kv::Value<'v>>,
+    {
+        fn len(&self) -> Option<usize> {
+            Some(<[(K, V)]>::len(self))
         }
 
-        fn u64(&mut self, v: u64) -> Result<(), Error> {
-            self.0.u64(v).map_err(Error::from_sval)
-        }
+        fn visit(
+            &self,
+            visit: &mut dyn FnMut(kv::Value<'v>, kv::Value<'v>) -> Result<(), Error>,
+        ) -> Result<(), Error> {
+            for &(k, v) in self {
+                visit(k.into(), v.into())?;
+            }
 
-        fn i64(&mut self, v: i64) -> Result<(), Error> {
-            self.0.i64(v).map_err(Error::from_sval)
+            Ok(())
         }
+    }
 
-        fn f64(&mut self, v: f64) -> Result<(), Error> {
-            self.0.f64(v).map_err(Error::from_sval)
+    impl<'v, V> VisitSeq<'v> for [V]
+    where
+        V: Copy + Into<kv::Value<'v>>,
+    {
+        fn len(&self) -> Option<usize> {
+            Some(<[V]>::len(self))
         }
 
-        fn bool(&mut self, v: bool) -> Result<(), Error> {
-            self.0.bool(v).map_err(Error::from_sval)
+        fn visit(
+            &self,
+            visit: &mut dyn FnMut(kv::Value<'v>) -> Result<(), Error>,
+        ) -> Result<(), Error> {
+            for &v in self {
+                visit(v.into())?;
+            }
+
+            Ok(())
         }
+    }
 
-        fn char(&mut self, v: char) -> Result<(), Error> {
-            self.0.char(v).map_err(Error::from_sval)
+    // `const N: usize` generics need Rust 1.51+; this is the same MSRV
+    // fixed-size arrays already relied on before this impl existed.
+    impl<'v, K, V, const N: usize> VisitMap<'v> for [(K, V); N]
+    where
+        K: Copy + Into<kv::Value<'v>>,
+        V: Copy + Into<kv::Value<'v>>,
+    {
+        fn len(&self) -> Option<usize> {
+            Some(N)
         }
 
-        fn str(&mut self, v: &str) -> Result<(), Error> {
-            self.0.str(v).map_err(Error::from_sval)
+        fn visit(
+            &self,
+            visit: &mut dyn FnMut(kv::Value<'v>, kv::Value<'v>) -> Result<(), Error>,
+        ) -> Result<(), Error> {
+            <[(K, V)] as VisitMap<'v>>::visit(self, visit)
         }
+    }
 
-        fn none(&mut self) -> Result<(), Error> {
-            self.0.none().map_err(Error::from_sval)
+    impl<'v, V, const N: usize> VisitSeq<'v> for [V; N]
+    where
+        V: Copy + Into<kv::Value<'v>>,
+    {
+        fn len(&self) -> Option<usize> {
+            Some(N)
         }
 
-        fn sval(&mut self, v: &dyn sval::Value) -> Result<(), Error> {
-            self.0.any(v).map_err(Error::from_sval)
+        fn visit(
+            &self,
+            visit: &mut dyn FnMut(kv::Value<'v>) -> Result<(), Error>,
+        ) -> Result<(), Error> {
+            <[V] as VisitSeq<'v>>::visit(self, visit)
         }
     }
 
-    pub(super) fn coerce<'v>(v: &dyn sval::Value) -> Coerced<'v> {
-        struct Coerce<'v>(Coerced<'v>);
+    #[cfg(feature = "std")]
+    mod std_support {
+        use super::*;
 
-        impl<'v> sval::Stream for Coerce<'v> {
-            fn u64(&mut self, v: u64) -> sval::stream::Result {
-                self.0 = Coerced::Primitive(Primitive::Unsigned(v));
-                Ok(())
+        use std::collections::{BTreeMap, HashMap};
+
+        impl<'v, K, V> VisitMap<'v> for Vec<(K, V)>
+        where
+            K: Copy + Into<kv::Value<'v>>,
+            V: Copy + Into<kv::Value<'v>>,
+        {
+            fn len(&self) -> Option<usize> {
+                Some(Vec::len(self))
             }
 
-            fn i64(&mut self, v: i64) -> sval::stream::Result {
-                self.0 = Coerced::Primitive(Primitive::Signed(v));
-                Ok(())
+            fn visit(
+                &self,
+                visit: &mut dyn FnMut(kv::Value<'v>, kv::Value<'v>) -> Result<(), Error>,
+            ) -> Result<(), Error> {
+                <[(K, V)] as VisitMap<'v>>::visit(self, visit)
             }
+        }
 
-            fn f64(&mut self, v: f64) -> sval::stream::Result {
-                self.0 = Coerced::Primitive(Primitive::Float(v));
-                Ok(())
+        impl<'v, V> VisitSeq<'v> for Vec<V>
+        where
+            V: Copy + Into<kv::Value<'v>>,
+        {
+            fn len(&self) -> Option<usize> {
+                Some(Vec::len(self))
             }
 
-            fn char(&mut self, v: char) -> sval::stream::Result {
-                self.0 = Coerced::Primitive(Primitive::Char(v));
-                Ok(())
+            fn visit(
+                &self,
+                visit: &mut dyn FnMut(kv::Value<'v>) -> Result<(), Error>,
+            ) -> Result<(), Error> {
+                <[V] as VisitSeq<'v>>::visit(self, visit)
             }
+        }
 
-            fn bool(&mut self, v: bool) -> sval::stream::Result {
-                self.0 = Coerced::Primitive(Primitive::Bool(v));
-                Ok(())
+        impl<'v, K, V> VisitMap<'v> for BTreeMap<K, V>
+        where
+            K: Copy + Into<kv::Value<'v>>,
+            V: Copy + Into<kv::Value<'v>>,
+        {
+            fn len(&self) -> Option<usize> {
+                Some(BTreeMap::len(self))
             }
 
-            #[cfg(feature = "std")]
-            fn str(&mut self, s: &str) -> sval::stream::Result {
-                self.0 = Coerced::String(s.into());
+            fn visit(
+                &self,
+                visit: &mut dyn FnMut(kv::Value<'v>, kv::Value<'v>) -> Result<(), Error>,
+            ) -> Result<(), Error> {
+                for (&k, &v) in self {
+                    visit(k.into(), v.into())?;
+                }
+
                 Ok(())
             }
         }
 
-        let mut coerce = Coerce(Coerced::Primitive(Primitive::None));
-        let _ = sval::stream(&mut coerce, v);
+        impl<'v, K, V> VisitMap<'v> for HashMap<K, V>
+        where
+            K: Copy + Into<kv::Value<'v>>,
+            V: Copy + Into<kv::Value<'v>>,
+        {
+            fn len(&self) -> Option<usize> {
+                Some(HashMap::len(self))
+            }
 
-        coerce.0
-    }
+            fn visit(
+                &self,
+                visit: &mut dyn FnMut(kv::Value<'v>, kv::Value<'v>) -> Result<(), Error>,
+            ) -> Result<(), Error> {
+                for (&k, &v) in self {
+                    visit(k.into(), v.into())?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+mod fmt_support {
+    use super::*;
+
+    use std::cell::Cell;
+
+    impl<'v> kv::Value<'v> {
+        /// Get a value from a debuggable type.
+        ///
+        /// Unlike [`capture_debug`](#method.capture_debug), this doesn't require
+        /// `T: 'static`, so it also accepts types that borrow (a struct holding a
+        /// `&str`, say). The tradeoff is that the value can't be recovered again
+        /// through [`downcast_ref`](#method.downcast_ref).
+        pub fn from_debug<T>(value: &'v T) -> Self
+        where
+            T: fmt::Debug,
+        {
+            kv::Value {
+                inner: Inner::Debug(value, None),
+            }
+        }
+
+        /// Get a value from a debuggable `'static` type.
+        ///
+        /// The value can later be recovered through
+        /// [`downcast_ref`](#method.downcast_ref). Whether `T` is `'static` has to
+        /// be known where this is called, since there's no way to recover an
+        /// `Any` handle for a type that might or might not be `'static` from
+        /// inside a single generic function.
+        pub fn capture_debug<T>(value: &'v T) -> Self
+        where
+            T: fmt::Debug + 'static,
+        {
+            kv::Value {
+                inner: Inner::Debug(value, Some(value as &dyn Any)),
+            }
+        }
+
+        /// Get a value from a displayable type.
+        ///
+        /// Unlike [`capture_display`](#method.capture_display), this doesn't
+        /// require `T: 'static`, so it also accepts types that borrow (a struct
+        /// holding a `&str`, say). The tradeoff is that the value can't be
+        /// recovered again through [`downcast_ref`](#method.downcast_ref).
+        pub fn from_display<T>(value: &'v T) -> Self
+        where
+            T: fmt::Display,
+        {
+            kv::Value {
+                inner: Inner::Display(value, None),
+            }
+        }
+
+        /// Get a value from a displayable `'static` type.
+        ///
+        /// The value can later be recovered through
+        /// [`downcast_ref`](#method.downcast_ref). Whether `T` is `'static` has to
+        /// be known where this is called, since there's no way to recover an
+        /// `Any` handle for a type that might or might not be `'static` from
+        /// inside a single generic function.
+        pub fn capture_display<T>(value: &'v T) -> Self
+        where
+            T: fmt::Display + 'static,
+        {
+            kv::Value {
+                inner: Inner::Display(value, Some(value as &dyn Any)),
+            }
+        }
+    }
+
+    impl<'v> fmt::Debug for kv::Value<'v> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.visit(&mut FmtVisitor(f, Cell::new(true)))?;
+
+            Ok(())
+        }
+    }
+
+    impl<'v> fmt::Display for kv::Value<'v> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.visit(&mut FmtVisitor(f, Cell::new(true)))?;
+
+            Ok(())
+        }
+    }
+
+    struct FmtVisitor<'a, 'b: 'a>(&'a mut fmt::Formatter<'b>, Cell<bool>);
+
+    impl<'a, 'b: 'a> Visitor for FmtVisitor<'a, 'b> {
+        fn debug(&mut self, v: &dyn fmt::Debug) -> Result<(), Error> {
+            v.fmt(self.0)?;
+
+            Ok(())
+        }
+
+        fn u64(&mut self, v: u64) -> Result<(), Error> {
+            self.debug(&format_args!("{:?}", v))
+        }
+
+        fn i64(&mut self, v: i64) -> Result<(), Error> {
+            self.debug(&format_args!("{:?}", v))
+        }
+
+        fn f64(&mut self, v: f64) -> Result<(), Error> {
+            self.debug(&format_args!("{:?}", v))
+        }
+
+        fn bool(&mut self, v: bool) -> Result<(), Error> {
+            self.debug(&format_args!("{:?}", v))
+        }
+
+        fn char(&mut self, v: char) -> Result<(), Error> {
+            self.debug(&format_args!("{:?}", v))
+        }
+
+        fn str(&mut self, v: &str) -> Result<(), Error> {
+            self.debug(&format_args!("{:?}", v))
+        }
+
+        fn bytes(&mut self, v: &[u8]) -> Result<(), Error> {
+            self.0.write_str("b\"")?;
+            for b in v {
+                write!(self.0, "\\x{:02x}", b)?;
+            }
+            self.0.write_str("\"")?;
+
+            Ok(())
+        }
+
+        fn none(&mut self) -> Result<(), Error> {
+            self.debug(&format_args!("None"))
+        }
+
+        fn map_begin(&mut self, _: Option<usize>) -> Result<(), Error> {
+            self.1.set(true);
+            self.0.write_str("{")?;
+
+            Ok(())
+        }
+
+        fn map_key(&mut self, k: kv::Value) -> Result<(), Error> {
+            if !self.1.get() {
+                self.0.write_str(", ")?;
+            }
+            self.1.set(false);
+
+            write!(self.0, "{:?}", k)?;
+
+            Ok(())
+        }
+
+        fn map_value(&mut self, v: kv::Value) -> Result<(), Error> {
+            self.0.write_str(": ")?;
+            write!(self.0, "{:?}", v)?;
+
+            Ok(())
+        }
+
+        fn map_end(&mut self) -> Result<(), Error> {
+            self.0.write_str("}")?;
+
+            Ok(())
+        }
+
+        fn seq_begin(&mut self, _: Option<usize>) -> Result<(), Error> {
+            self.1.set(true);
+            self.0.write_str("[")?;
+
+            Ok(())
+        }
+
+        fn seq_elem(&mut self, v: kv::Value) -> Result<(), Error> {
+            if !self.1.get() {
+                self.0.write_str(", ")?;
+            }
+            self.1.set(false);
+
+            write!(self.0, "{:?}", v)?;
+
+            Ok(())
+        }
+
+        fn seq_end(&mut self) -> Result<(), Error> {
+            self.0.write_str("]")?;
+
+            Ok(())
+        }
+
+        #[cfg(feature = "kv_unstable_sval")]
+        fn sval(&mut self, v: &dyn sval_support::Value) -> Result<(), Error> {
+            sval_support::fmt(self.0, v)
+        }
+
+        #[cfg(feature = "kv_unstable_serde")]
+        fn serde(&mut self, v: &dyn erased_serde::Serialize) -> Result<(), Error> {
+            serde_support::fmt(self.0, v)
+        }
+    }
+}
+
+#[cfg(feature = "kv_unstable_sval")]
+pub(super) mod sval_support {
+    use super::coerce::Coerced;
+    use super::*;
+
+    extern crate sval;
+
+    impl<'v> kv::Value<'v> {
+        /// Get a value from a structured type.
+        pub fn from_sval<T>(value: &'v T) -> Self
+        where
+            T: sval::Value,
+        {
+            kv::Value {
+                inner: Inner::Sval(value),
+            }
+        }
+    }
+
+    impl<'v> sval::Value for kv::Value<'v> {
+        fn stream(&self, s: &mut sval::value::Stream) -> sval::value::Result {
+            self.visit(&mut SvalVisitor(s)).map_err(Error::into_sval)?;
+
+            Ok(())
+        }
+    }
+
+    pub(in kv::value) use self::sval::Value;
+
+    pub(super) fn fmt(f: &mut fmt::Formatter, v: &dyn sval::Value) -> Result<(), Error> {
+        sval::fmt::debug(f, v)?;
+        Ok(())
+    }
+
+    impl Error {
+        fn from_sval(_: sval::value::Error) -> Self {
+            Error::msg("`sval` serialization failed")
+        }
+
+        fn into_sval(self) -> sval::value::Error {
+            sval::value::Error::msg("`sval` serialization failed")
+        }
+    }
+
+    struct SvalVisitor<'a, 'b: 'a>(&'a mut sval::value::Stream<'b>);
+
+    impl<'a, 'b: 'a> Visitor for SvalVisitor<'a, 'b> {
+        fn debug(&mut self, v: &dyn fmt::Debug) -> Result<(), Error> {
+            self.0
+                .fmt(format_args!("{:?}", v))
+                .map_err(Error::from_sval)
+        }
+
+        fn u64(&mut self, v: u64) -> Result<(), Error> {
+            self.0.u64(v).map_err(Error::from_sval)
+        }
+
+        fn i64(&mut self, v: i64) -> Result<(), Error> {
+            self.0.i64(v).map_err(Error::from_sval)
+        }
+
+        fn f64(&mut self, v: f64) -> Result<(), Error> {
+            self.0.f64(v).map_err(Error::from_sval)
+        }
+
+        fn bool(&mut self, v: bool) -> Result<(), Error> {
+            self.0.bool(v).map_err(Error::from_sval)
+        }
+
+        fn char(&mut self, v: char) -> Result<(), Error> {
+            self.0.char(v).map_err(Error::from_sval)
+        }
+
+        fn str(&mut self, v: &str) -> Result<(), Error> {
+            self.0.str(v).map_err(Error::from_sval)
+        }
+
+        fn bytes(&mut self, v: &[u8]) -> Result<(), Error> {
+            // `sval` 0.4 has no dedicated byte-string primitive, so stream it
+            // as a seq of `u8`s rather than falling back to `Debug`.
+            self.0.seq_begin(Some(v.len())).map_err(Error::from_sval)?;
+            for &b in v {
+                self.0.seq_elem(b).map_err(Error::from_sval)?;
+            }
+            self.0.seq_end().map_err(Error::from_sval)
+        }
+
+        fn none(&mut self) -> Result<(), Error> {
+            self.0.none().map_err(Error::from_sval)
+        }
+
+        fn sval(&mut self, v: &dyn sval::Value) -> Result<(), Error> {
+            self.0.any(v).map_err(Error::from_sval)
+        }
+
+        fn map_begin(&mut self, len: Option<usize>) -> Result<(), Error> {
+            self.0.map_begin(len).map_err(Error::from_sval)
+        }
+
+        fn map_key(&mut self, k: kv::Value) -> Result<(), Error> {
+            self.0.map_key(k).map_err(Error::from_sval)
+        }
+
+        fn map_value(&mut self, v: kv::Value) -> Result<(), Error> {
+            self.0.map_value(v).map_err(Error::from_sval)
+        }
+
+        fn map_end(&mut self) -> Result<(), Error> {
+            self.0.map_end().map_err(Error::from_sval)
+        }
+
+        fn seq_begin(&mut self, len: Option<usize>) -> Result<(), Error> {
+            self.0.seq_begin(len).map_err(Error::from_sval)
+        }
+
+        fn seq_elem(&mut self, v: kv::Value) -> Result<(), Error> {
+            self.0.seq_elem(v).map_err(Error::from_sval)
+        }
+
+        fn seq_end(&mut self) -> Result<(), Error> {
+            self.0.seq_end().map_err(Error::from_sval)
+        }
+
+        #[cfg(feature = "kv_unstable_serde")]
+        fn serde(&mut self, v: &dyn erased_serde::Serialize) -> Result<(), Error> {
+            struct SvalFromSerde<'a>(&'a dyn erased_serde::Serialize);
+
+            impl<'a> fmt::Debug for SvalFromSerde<'a> {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    serde_support::fmt(f, self.0).map_err(|_| fmt::Error)
+                }
+            }
+
+            self.debug(&SvalFromSerde(v))
+        }
+    }
+
+    pub(super) fn coerce<'v>(v: &dyn sval::Value) -> Coerced<'v> {
+        struct Coerce<'v>(Coerced<'v>);
+
+        impl<'v> sval::Stream for Coerce<'v> {
+            fn u64(&mut self, v: u64) -> sval::stream::Result {
+                self.0 = Coerced::Primitive(Primitive::Unsigned(v));
+                Ok(())
+            }
+
+            fn i64(&mut self, v: i64) -> sval::stream::Result {
+                self.0 = Coerced::Primitive(Primitive::Signed(v));
+                Ok(())
+            }
+
+            fn f64(&mut self, v: f64) -> sval::stream::Result {
+                self.0 = Coerced::Primitive(Primitive::Float(v));
+                Ok(())
+            }
+
+            fn char(&mut self, v: char) -> sval::stream::Result {
+                self.0 = Coerced::Primitive(Primitive::Char(v));
+                Ok(())
+            }
+
+            fn bool(&mut self, v: bool) -> sval::stream::Result {
+                self.0 = Coerced::Primitive(Primitive::Bool(v));
+                Ok(())
+            }
+
+            #[cfg(feature = "std")]
+            fn str(&mut self, s: &str) -> sval::stream::Result {
+                self.0 = Coerced::String(s.into());
+                Ok(())
+            }
+        }
+
+        let mut coerce = Coerce(Coerced::Primitive(Primitive::None));
+        let _ = sval::stream(&mut coerce, v);
+
+        coerce.0
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use kv::value::test::Token;
+
+        #[test]
+        fn test_from_sval() {
+            assert_eq!(kv::Value::from_sval(&42u64).to_token(), Token::Sval);
+        }
+
+        #[test]
+        fn test_sval_structured() {
+            let value = kv::Value::from(42u64);
+            let expected = vec![sval::test::Token::Unsigned(42)];
+
+            assert_eq!(sval::test::tokens(value), expected);
+        }
+
+        #[test]
+        fn coersion() {
+            assert_eq!(
+                42u64,
+                kv::Value::from_sval(&42u64)
+                    .as_u64()
+                    .expect("invalid value")
+            );
+
+            assert!(kv::Value::from_sval(&"a string").as_str().is_none());
+
+            #[cfg(feature = "std")]
+            assert_eq!(
+                "a string",
+                &*kv::Value::from_sval(&"a string")
+                    .to_str()
+                    .expect("invalid value")
+            );
+        }
+    }
+}
+
+#[cfg(feature = "kv_unstable_serde")]
+pub(super) mod serde_support {
+    use super::coerce::Coerced;
+    use super::*;
+
+    extern crate erased_serde;
+    extern crate serde;
+
+    impl<'v> kv::Value<'v> {
+        /// Get a value from a structured type.
+        pub fn from_serde<T>(value: &'v T) -> Self
+        where
+            T: serde::Serialize,
+        {
+            kv::Value {
+                inner: Inner::Serde(value),
+            }
+        }
+    }
+
+    impl<'v> serde::Serialize for kv::Value<'v> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let mut visitor = SerdeVisitor::new(serializer);
+            self.visit(&mut visitor).map_err(Error::into_serde)?;
+
+            visitor.finish()
+        }
+    }
+
+    impl Error {
+        fn from_serde<E>(_: E) -> Self
+        where
+            E: fmt::Display,
+        {
+            Error::msg("`serde` serialization failed")
+        }
+
+        fn into_serde<E>(self) -> E
+        where
+            E: serde::ser::Error,
+        {
+            E::custom(self)
+        }
+    }
+
+    use serde::ser::{SerializeMap as _, SerializeSeq as _};
+
+    /// Drives the internal `Visitor` calls into a `serde::Serializer`.
+    ///
+    /// Primitives (and `debug`/`serde`) are a single call into the `Visitor`, so
+    /// the serializer is threaded through as a `State` and taken the first time
+    /// it's needed. Maps and sequences are a `begin`/`key`/`value`/`end` sequence
+    /// of calls, so the in-progress `SerializeMap`/`SerializeSeq` is held in the
+    /// same `State` between calls instead.
+    struct SerdeVisitor<S: serde::Serializer> {
+        state: Option<SerdeVisitorState<S>>,
+    }
+
+    enum SerdeVisitorState<S: serde::Serializer> {
+        Ready(S),
+        Map(S::SerializeMap),
+        Seq(S::SerializeSeq),
+        Done(Result<S::Ok, S::Error>),
+    }
+
+    impl<S: serde::Serializer> SerdeVisitor<S> {
+        fn new(ser: S) -> Self {
+            SerdeVisitor {
+                state: Some(SerdeVisitorState::Ready(ser)),
+            }
+        }
+
+        fn finish(self) -> Result<S::Ok, S::Error> {
+            match self.state {
+                Some(SerdeVisitorState::Done(result)) => result,
+                // A `Fill` impl that never visits the slot drives the `Value`
+                // to completion without ever reaching `Done`. An untrusted
+                // `Value` shouldn't be able to panic a caller's serializer.
+                _ => Err(<S::Error as serde::ser::Error>::custom(
+                    "a value wasn't serialized",
+                )),
+            }
+        }
+
+        fn serialize(&mut self, f: impl FnOnce(S) -> Result<S::Ok, S::Error>) -> Result<(), Error> {
+            match self.state.take() {
+                Some(SerdeVisitorState::Ready(ser)) => {
+                    self.state = Some(SerdeVisitorState::Done(f(ser)));
+                    Ok(())
+                }
+                _ => Err(Error::msg("attempt to serialize a value twice")),
+            }
+        }
+    }
+
+    impl<S: serde::Serializer> Visitor for SerdeVisitor<S> {
+        fn debug(&mut self, v: &dyn fmt::Debug) -> Result<(), Error> {
+            self.serialize(|ser| ser.collect_str(&format_args!("{:?}", v)))
+        }
+
+        fn u64(&mut self, v: u64) -> Result<(), Error> {
+            self.serialize(|ser| ser.serialize_u64(v))
+        }
+
+        fn i64(&mut self, v: i64) -> Result<(), Error> {
+            self.serialize(|ser| ser.serialize_i64(v))
+        }
+
+        fn f64(&mut self, v: f64) -> Result<(), Error> {
+            self.serialize(|ser| ser.serialize_f64(v))
+        }
+
+        fn bool(&mut self, v: bool) -> Result<(), Error> {
+            self.serialize(|ser| ser.serialize_bool(v))
+        }
+
+        fn char(&mut self, v: char) -> Result<(), Error> {
+            self.serialize(|ser| ser.serialize_char(v))
+        }
+
+        fn str(&mut self, v: &str) -> Result<(), Error> {
+            self.serialize(|ser| ser.serialize_str(v))
+        }
+
+        fn bytes(&mut self, v: &[u8]) -> Result<(), Error> {
+            self.serialize(|ser| ser.serialize_bytes(v))
+        }
+
+        fn none(&mut self) -> Result<(), Error> {
+            self.serialize(|ser| ser.serialize_none())
+        }
+
+        fn map_begin(&mut self, len: Option<usize>) -> Result<(), Error> {
+            match self.state.take() {
+                Some(SerdeVisitorState::Ready(ser)) => {
+                    let map = ser.serialize_map(len).map_err(Error::from_serde)?;
+                    self.state = Some(SerdeVisitorState::Map(map));
+                    Ok(())
+                }
+                _ => Err(Error::msg("attempt to serialize a value twice")),
+            }
+        }
+
+        fn map_key(&mut self, k: kv::Value) -> Result<(), Error> {
+            match &mut self.state {
+                Some(SerdeVisitorState::Map(map)) => {
+                    map.serialize_key(&k).map_err(Error::from_serde)
+                }
+                _ => Err(Error::msg("`map_key` called outside of a map")),
+            }
+        }
+
+        fn map_value(&mut self, v: kv::Value) -> Result<(), Error> {
+            match &mut self.state {
+                Some(SerdeVisitorState::Map(map)) => {
+                    map.serialize_value(&v).map_err(Error::from_serde)
+                }
+                _ => Err(Error::msg("`map_value` called outside of a map")),
+            }
+        }
+
+        fn map_end(&mut self) -> Result<(), Error> {
+            match self.state.take() {
+                Some(SerdeVisitorState::Map(map)) => {
+                    self.state = Some(SerdeVisitorState::Done(map.end()));
+                    Ok(())
+                }
+                _ => Err(Error::msg("`map_end` called outside of a map")),
+            }
+        }
+
+        fn seq_begin(&mut self, len: Option<usize>) -> Result<(), Error> {
+            match self.state.take() {
+                Some(SerdeVisitorState::Ready(ser)) => {
+                    let seq = ser.serialize_seq(len).map_err(Error::from_serde)?;
+                    self.state = Some(SerdeVisitorState::Seq(seq));
+                    Ok(())
+                }
+                _ => Err(Error::msg("attempt to serialize a value twice")),
+            }
+        }
+
+        fn seq_elem(&mut self, v: kv::Value) -> Result<(), Error> {
+            match &mut self.state {
+                Some(SerdeVisitorState::Seq(seq)) => {
+                    seq.serialize_element(&v).map_err(Error::from_serde)
+                }
+                _ => Err(Error::msg("`seq_elem` called outside of a sequence")),
+            }
+        }
+
+        fn seq_end(&mut self) -> Result<(), Error> {
+            match self.state.take() {
+                Some(SerdeVisitorState::Seq(seq)) => {
+                    self.state = Some(SerdeVisitorState::Done(seq.end()));
+                    Ok(())
+                }
+                _ => Err(Error::msg("`seq_end` called outside of a sequence")),
+            }
+        }
+
+        #[cfg(feature = "kv_unstable_sval")]
+        fn sval(&mut self, v: &dyn sval_support::Value) -> Result<(), Error> {
+            struct SerdeFromSval<'a>(&'a dyn sval_support::Value);
+
+            impl<'a> fmt::Debug for SerdeFromSval<'a> {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    sval_support::fmt(f, self.0).map_err(|_| fmt::Error)
+                }
+            }
+
+            self.debug(&SerdeFromSval(v))
+        }
+
+        fn serde(&mut self, v: &dyn erased_serde::Serialize) -> Result<(), Error> {
+            self.serialize(|ser| erased_serde::serialize(v, ser))
+        }
+    }
+
+    /// Format a `serde::Serialize` value using a minimal JSON-like serializer.
+    pub(super) fn fmt(f: &mut fmt::Formatter, v: &dyn erased_serde::Serialize) -> Result<(), Error> {
+        erased_serde::serialize(v, &mut FmtSerializer(f)).map_err(Error::from_serde)
+    }
+
+    struct FmtSerializer<'a, 'b: 'a>(&'a mut fmt::Formatter<'b>);
+
+    #[derive(Debug)]
+    struct FmtError;
+
+    impl fmt::Display for FmtError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("formatting a `serde` value failed")
+        }
+    }
+
+    impl std::error::Error for FmtError {}
+
+    impl serde::ser::Error for FmtError {
+        fn custom<T>(_: T) -> Self
+        where
+            T: fmt::Display,
+        {
+            FmtError
+        }
+    }
+
+    impl From<fmt::Error> for FmtError {
+        fn from(_: fmt::Error) -> Self {
+            FmtError
+        }
+    }
+
+    struct FmtSeq<'a, 'b: 'a> {
+        fmt: &'a mut fmt::Formatter<'b>,
+        first: bool,
+    }
+
+    struct FmtMap<'a, 'b: 'a> {
+        fmt: &'a mut fmt::Formatter<'b>,
+        first: bool,
+    }
+
+    impl<'a, 'b: 'a, 'c> serde::Serializer for &'c mut FmtSerializer<'a, 'b> {
+        type Ok = ();
+        type Error = FmtError;
+        type SerializeSeq = FmtSeq<'a, 'b>;
+        type SerializeTuple = FmtSeq<'a, 'b>;
+        type SerializeTupleStruct = FmtSeq<'a, 'b>;
+        type SerializeTupleVariant = FmtSeq<'a, 'b>;
+        type SerializeMap = FmtMap<'a, 'b>;
+        type SerializeStruct = FmtMap<'a, 'b>;
+        type SerializeStructVariant = FmtMap<'a, 'b>;
+
+        fn serialize_bool(self, v: bool) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", v)?)
+        }
+
+        fn serialize_i8(self, v: i8) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", v)?)
+        }
+
+        fn serialize_i16(self, v: i16) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", v)?)
+        }
+
+        fn serialize_i32(self, v: i32) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", v)?)
+        }
+
+        fn serialize_i64(self, v: i64) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", v)?)
+        }
+
+        fn serialize_u8(self, v: u8) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", v)?)
+        }
+
+        fn serialize_u16(self, v: u16) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", v)?)
+        }
+
+        fn serialize_u32(self, v: u32) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", v)?)
+        }
+
+        fn serialize_u64(self, v: u64) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", v)?)
+        }
+
+        fn serialize_f32(self, v: f32) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", v)?)
+        }
+
+        fn serialize_f64(self, v: f64) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", v)?)
+        }
+
+        fn serialize_char(self, v: char) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", v)?)
+        }
+
+        fn serialize_str(self, v: &str) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", v)?)
+        }
+
+        fn serialize_bytes(self, v: &[u8]) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", v)?)
+        }
+
+        fn serialize_none(self) -> Result<(), FmtError> {
+            Ok(write!(self.0, "None")?)
+        }
+
+        fn serialize_some<T: ?Sized>(self, value: &T) -> Result<(), FmtError>
+        where
+            T: serde::Serialize,
+        {
+            value.serialize(self)
+        }
+
+        fn serialize_unit(self) -> Result<(), FmtError> {
+            Ok(write!(self.0, "None")?)
+        }
+
+        fn serialize_unit_struct(self, name: &'static str) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", name)?)
+        }
+
+        fn serialize_unit_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            variant: &'static str,
+        ) -> Result<(), FmtError> {
+            Ok(write!(self.0, "{:?}", variant)?)
+        }
+
+        fn serialize_newtype_struct<T: ?Sized>(
+            self,
+            _: &'static str,
+            value: &T,
+        ) -> Result<(), FmtError>
+        where
+            T: serde::Serialize,
+        {
+            value.serialize(self)
+        }
+
+        fn serialize_newtype_variant<T: ?Sized>(
+            self,
+            _: &'static str,
+            _: u32,
+            variant: &'static str,
+            value: &T,
+        ) -> Result<(), FmtError>
+        where
+            T: serde::Serialize,
+        {
+            write!(self.0, "{}(", variant)?;
+            value.serialize(&mut FmtSerializer(self.0))?;
+            Ok(write!(self.0, ")")?)
+        }
+
+        fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, FmtError> {
+            write!(self.0, "[")?;
+            Ok(FmtSeq {
+                fmt: self.0,
+                first: true,
+            })
+        }
+
+        fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, FmtError> {
+            self.serialize_seq(Some(len))
+        }
+
+        fn serialize_tuple_struct(
+            self,
+            _: &'static str,
+            len: usize,
+        ) -> Result<Self::SerializeTupleStruct, FmtError> {
+            self.serialize_seq(Some(len))
+        }
+
+        fn serialize_tuple_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            variant: &'static str,
+            len: usize,
+        ) -> Result<Self::SerializeTupleVariant, FmtError> {
+            write!(self.0, "{}", variant)?;
+            self.serialize_seq(Some(len))
+        }
+
+        fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, FmtError> {
+            write!(self.0, "{{")?;
+            Ok(FmtMap {
+                fmt: self.0,
+                first: true,
+            })
+        }
+
+        fn serialize_struct(
+            self,
+            _: &'static str,
+            len: usize,
+        ) -> Result<Self::SerializeStruct, FmtError> {
+            self.serialize_map(Some(len))
+        }
+
+        fn serialize_struct_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            variant: &'static str,
+            len: usize,
+        ) -> Result<Self::SerializeStructVariant, FmtError> {
+            write!(self.0, "{}", variant)?;
+            self.serialize_map(Some(len))
+        }
+    }
+
+    impl<'a, 'b: 'a> serde::ser::SerializeSeq for FmtSeq<'a, 'b> {
+        type Ok = ();
+        type Error = FmtError;
+
+        fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), FmtError>
+        where
+            T: serde::Serialize,
+        {
+            if !self.first {
+                write!(self.fmt, ", ")?;
+            }
+            self.first = false;
+
+            value.serialize(&mut FmtSerializer(self.fmt))
+        }
+
+        fn end(self) -> Result<(), FmtError> {
+            Ok(write!(self.fmt, "]")?)
+        }
+    }
+
+    impl<'a, 'b: 'a> serde::ser::SerializeTuple for FmtSeq<'a, 'b> {
+        type Ok = ();
+        type Error = FmtError;
+
+        fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), FmtError>
+        where
+            T: serde::Serialize,
+        {
+            serde::ser::SerializeSeq::serialize_element(self, value)
+        }
+
+        fn end(self) -> Result<(), FmtError> {
+            serde::ser::SerializeSeq::end(self)
+        }
+    }
+
+    impl<'a, 'b: 'a> serde::ser::SerializeTupleStruct for FmtSeq<'a, 'b> {
+        type Ok = ();
+        type Error = FmtError;
+
+        fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), FmtError>
+        where
+            T: serde::Serialize,
+        {
+            serde::ser::SerializeSeq::serialize_element(self, value)
+        }
+
+        fn end(self) -> Result<(), FmtError> {
+            serde::ser::SerializeSeq::end(self)
+        }
+    }
+
+    impl<'a, 'b: 'a> serde::ser::SerializeTupleVariant for FmtSeq<'a, 'b> {
+        type Ok = ();
+        type Error = FmtError;
+
+        fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), FmtError>
+        where
+            T: serde::Serialize,
+        {
+            serde::ser::SerializeSeq::serialize_element(self, value)
+        }
+
+        fn end(self) -> Result<(), FmtError> {
+            serde::ser::SerializeSeq::end(self)
+        }
+    }
+
+    impl<'a, 'b: 'a> serde::ser::SerializeMap for FmtMap<'a, 'b> {
+        type Ok = ();
+        type Error = FmtError;
+
+        fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<(), FmtError>
+        where
+            T: serde::Serialize,
+        {
+            if !self.first {
+                write!(self.fmt, ", ")?;
+            }
+            self.first = false;
+
+            key.serialize(&mut FmtSerializer(self.fmt))
+        }
+
+        fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<(), FmtError>
+        where
+            T: serde::Serialize,
+        {
+            write!(self.fmt, ": ")?;
+            value.serialize(&mut FmtSerializer(self.fmt))
+        }
+
+        fn end(self) -> Result<(), FmtError> {
+            Ok(write!(self.fmt, "}}")?)
+        }
+    }
+
+    impl<'a, 'b: 'a> serde::ser::SerializeStruct for FmtMap<'a, 'b> {
+        type Ok = ();
+        type Error = FmtError;
+
+        fn serialize_field<T: ?Sized>(
+            &mut self,
+            key: &'static str,
+            value: &T,
+        ) -> Result<(), FmtError>
+        where
+            T: serde::Serialize,
+        {
+            if !self.first {
+                write!(self.fmt, ", ")?;
+            }
+            self.first = false;
+
+            write!(self.fmt, "{}: ", key)?;
+            value.serialize(&mut FmtSerializer(self.fmt))
+        }
+
+        fn end(self) -> Result<(), FmtError> {
+            Ok(write!(self.fmt, "}}")?)
+        }
+    }
+
+    impl<'a, 'b: 'a> serde::ser::SerializeStructVariant for FmtMap<'a, 'b> {
+        type Ok = ();
+        type Error = FmtError;
+
+        fn serialize_field<T: ?Sized>(
+            &mut self,
+            key: &'static str,
+            value: &T,
+        ) -> Result<(), FmtError>
+        where
+            T: serde::Serialize,
+        {
+            serde::ser::SerializeStruct::serialize_field(self, key, value)
+        }
+
+        fn end(self) -> Result<(), FmtError> {
+            serde::ser::SerializeStruct::end(self)
+        }
+    }
+
+    /// Stream a `serde::Serialize` value through a minimal serializer that only
+    /// collects the first primitive it sees, mirroring `sval_support::coerce`.
+    pub(super) fn coerce<'v>(v: &dyn erased_serde::Serialize) -> Coerced<'v> {
+        use serde::ser::{Error as SerError, Impossible};
+
+        struct Coerce<'v>(Coerced<'v>);
+
+        #[derive(Debug)]
+        struct CoerceError;
+
+        impl fmt::Display for CoerceError {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("the value couldn't be coerced")
+            }
+        }
+
+        impl std::error::Error for CoerceError {}
+
+        impl SerError for CoerceError {
+            fn custom<T>(_: T) -> Self
+            where
+                T: fmt::Display,
+            {
+                CoerceError
+            }
+        }
+
+        impl<'a, 'v> serde::Serializer for &'a mut Coerce<'v> {
+            type Ok = ();
+            type Error = CoerceError;
+            type SerializeSeq = Impossible<(), CoerceError>;
+            type SerializeTuple = Impossible<(), CoerceError>;
+            type SerializeTupleStruct = Impossible<(), CoerceError>;
+            type SerializeTupleVariant = Impossible<(), CoerceError>;
+            type SerializeMap = Impossible<(), CoerceError>;
+            type SerializeStruct = Impossible<(), CoerceError>;
+            type SerializeStructVariant = Impossible<(), CoerceError>;
+
+            fn serialize_bool(self, v: bool) -> Result<(), CoerceError> {
+                self.0 = Coerced::Primitive(Primitive::Bool(v));
+                Ok(())
+            }
+
+            fn serialize_i8(self, v: i8) -> Result<(), CoerceError> {
+                self.serialize_i64(v as i64)
+            }
+
+            fn serialize_i16(self, v: i16) -> Result<(), CoerceError> {
+                self.serialize_i64(v as i64)
+            }
+
+            fn serialize_i32(self, v: i32) -> Result<(), CoerceError> {
+                self.serialize_i64(v as i64)
+            }
+
+            fn serialize_i64(self, v: i64) -> Result<(), CoerceError> {
+                self.0 = Coerced::Primitive(Primitive::Signed(v));
+                Ok(())
+            }
+
+            fn serialize_u8(self, v: u8) -> Result<(), CoerceError> {
+                self.serialize_u64(v as u64)
+            }
+
+            fn serialize_u16(self, v: u16) -> Result<(), CoerceError> {
+                self.serialize_u64(v as u64)
+            }
+
+            fn serialize_u32(self, v: u32) -> Result<(), CoerceError> {
+                self.serialize_u64(v as u64)
+            }
+
+            fn serialize_u64(self, v: u64) -> Result<(), CoerceError> {
+                self.0 = Coerced::Primitive(Primitive::Unsigned(v));
+                Ok(())
+            }
+
+            fn serialize_f32(self, v: f32) -> Result<(), CoerceError> {
+                self.serialize_f64(v as f64)
+            }
+
+            fn serialize_f64(self, v: f64) -> Result<(), CoerceError> {
+                self.0 = Coerced::Primitive(Primitive::Float(v));
+                Ok(())
+            }
+
+            fn serialize_char(self, v: char) -> Result<(), CoerceError> {
+                self.0 = Coerced::Primitive(Primitive::Char(v));
+                Ok(())
+            }
+
+            #[cfg(feature = "std")]
+            fn serialize_str(self, v: &str) -> Result<(), CoerceError> {
+                self.0 = Coerced::String(v.into());
+                Ok(())
+            }
+
+            #[cfg(not(feature = "std"))]
+            fn serialize_str(self, _: &str) -> Result<(), CoerceError> {
+                Ok(())
+            }
+
+            #[cfg(feature = "std")]
+            fn serialize_bytes(self, v: &[u8]) -> Result<(), CoerceError> {
+                self.0 = Coerced::Bytes(v.to_vec());
+                Ok(())
+            }
+
+            #[cfg(not(feature = "std"))]
+            fn serialize_bytes(self, _: &[u8]) -> Result<(), CoerceError> {
+                Ok(())
+            }
+
+            fn serialize_none(self) -> Result<(), CoerceError> {
+                self.0 = Coerced::Primitive(Primitive::None);
+                Ok(())
+            }
+
+            fn serialize_some<T: ?Sized>(self, value: &T) -> Result<(), CoerceError>
+            where
+                T: serde::Serialize,
+            {
+                value.serialize(self)
+            }
+
+            fn serialize_unit(self) -> Result<(), CoerceError> {
+                self.0 = Coerced::Primitive(Primitive::None);
+                Ok(())
+            }
+
+            fn serialize_unit_struct(self, _: &'static str) -> Result<(), CoerceError> {
+                self.serialize_unit()
+            }
+
+            fn serialize_unit_variant(
+                self,
+                _: &'static str,
+                _: u32,
+                variant: &'static str,
+            ) -> Result<(), CoerceError> {
+                self.serialize_str(variant)
+            }
+
+            fn serialize_newtype_struct<T: ?Sized>(
+                self,
+                _: &'static str,
+                value: &T,
+            ) -> Result<(), CoerceError>
+            where
+                T: serde::Serialize,
+            {
+                value.serialize(self)
+            }
+
+            fn serialize_newtype_variant<T: ?Sized>(
+                self,
+                _: &'static str,
+                _: u32,
+                _: &'static str,
+                _: &T,
+            ) -> Result<(), CoerceError>
+            where
+                T: serde::Serialize,
+            {
+                Err(CoerceError)
+            }
+
+            fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, CoerceError> {
+                Err(CoerceError)
+            }
+
+            fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, CoerceError> {
+                Err(CoerceError)
+            }
+
+            fn serialize_tuple_struct(
+                self,
+                _: &'static str,
+                _: usize,
+            ) -> Result<Self::SerializeTupleStruct, CoerceError> {
+                Err(CoerceError)
+            }
+
+            fn serialize_tuple_variant(
+                self,
+                _: &'static str,
+                _: u32,
+                _: &'static str,
+                _: usize,
+            ) -> Result<Self::SerializeTupleVariant, CoerceError> {
+                Err(CoerceError)
+            }
+
+            fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, CoerceError> {
+                Err(CoerceError)
+            }
+
+            fn serialize_struct(
+                self,
+                _: &'static str,
+                _: usize,
+            ) -> Result<Self::SerializeStruct, CoerceError> {
+                Err(CoerceError)
+            }
+
+            fn serialize_struct_variant(
+                self,
+                _: &'static str,
+                _: u32,
+                _: &'static str,
+                _: usize,
+            ) -> Result<Self::SerializeStructVariant, CoerceError> {
+                Err(CoerceError)
+            }
+        }
+
+        let mut coerce = Coerce(Coerced::Primitive(Primitive::None));
+        let _ = erased_serde::serialize(v, &mut coerce);
+
+        coerce.0
+    }
 
     #[cfg(test)]
     mod tests {
@@ -483,33 +1994,25 @@ pub(super) mod sval_support {
         use kv::value::test::Token;
 
         #[test]
-        fn test_from_sval() {
-            assert_eq!(kv::Value::from_sval(&42u64).to_token(), Token::Sval);
-        }
-
-        #[test]
-        fn test_sval_structured() {
-            let value = kv::Value::from(42u64);
-            let expected = vec![sval::test::Token::Unsigned(42)];
-
-            assert_eq!(sval::test::tokens(value), expected);
+        fn test_from_serde() {
+            assert_eq!(kv::Value::from_serde(&42u64).to_token(), Token::Serde);
         }
 
         #[test]
         fn coersion() {
             assert_eq!(
                 42u64,
-                kv::Value::from_sval(&42u64)
+                kv::Value::from_serde(&42u64)
                     .as_u64()
                     .expect("invalid value")
             );
 
-            assert!(kv::Value::from_sval(&"a string").as_str().is_none());
+            assert!(kv::Value::from_serde(&"a string").as_str().is_none());
 
             #[cfg(feature = "std")]
             assert_eq!(
                 "a string",
-                &*kv::Value::from_sval(&"a string")
+                &*kv::Value::from_serde(&"a string")
                     .to_str()
                     .expect("invalid value")
             );